@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+const INDEX_FILE: &str = "index.json";
+
+/// A single config file's last-known fingerprint.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub path: String,
+    pub size: u64,
+    pub mtime: u64,
+    pub hash: String,
+}
+
+/// `path -> fingerprint` map, persisted to `app_local_data_dir()/index.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanIndex(pub HashMap<String, IndexEntry>);
+
+pub struct ScanIndexState(pub Mutex<ScanIndex>);
+
+/// Result of comparing a fresh scan against the stored index.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScanDiff {
+    pub added: Vec<IndexEntry>,
+    pub modified: Vec<IndexEntry>,
+    pub removed: Vec<String>,
+}
+
+fn index_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(INDEX_FILE))
+}
+
+/// Loads the persisted index, falling back to an empty index on a missing or corrupted file
+/// (e.g. truncated by a crash mid-write, since `std::fs::write` isn't atomic) rather than
+/// failing -- this is called from `setup`, where an `Err` would abort app startup entirely.
+/// Falling back just means the next scan re-hashes everything once, which is harmless.
+pub fn load_index(app: &AppHandle) -> Result<ScanIndex, String> {
+    let path = index_path(app)?;
+    if !path.exists() {
+        return Ok(ScanIndex::default());
+    }
+    let raw = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    match serde_json::from_str(&raw) {
+        Ok(index) => Ok(index),
+        Err(e) => {
+            eprintln!("failed to parse {}: {e}; falling back to an empty index", path.display());
+            Ok(ScanIndex::default())
+        }
+    }
+}
+
+pub fn save_index(app: &AppHandle, index: &ScanIndex) -> Result<(), String> {
+    let path = index_path(app)?;
+    let raw = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+    std::fs::write(path, raw).map_err(|e| e.to_string())
+}
+
+fn hash_file(path: &str) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    Ok(blake3::hash(&bytes).to_hex().to_string())
+}
+
+/// Walks `current_files`, re-hashing only entries whose `(size, mtime)` changed since the
+/// last scan, and returns the set of additions/modifications plus any paths that vanished.
+///
+/// A file can legitimately disappear between the `WalkDir` pass that produced
+/// `current_files` and the `stat`/read here (editor swap-files, atomic-rename saves, a live
+/// watcher racing the scan). Such a file is treated the same as one that was never found by
+/// the walk at all -- it simply falls out into `removed` below -- rather than aborting the
+/// whole scan for every other file.
+pub fn diff_against_index(
+    index: &mut ScanIndex,
+    current_files: &[String],
+) -> Result<ScanDiff, String> {
+    let mut diff = ScanDiff::default();
+    let mut seen = std::collections::HashSet::new();
+
+    for path in current_files {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            continue;
+        };
+        let Some(mtime) = metadata.modified().ok().and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        else {
+            continue;
+        };
+        let size = metadata.len();
+        let mtime = mtime.as_secs();
+
+        match index.0.get(path) {
+            Some(existing) if existing.size == size && existing.mtime == mtime => {
+                // Unchanged metadata: trust the cached hash and skip re-reading the file.
+                seen.insert(path.clone());
+            }
+            Some(_) => match hash_file(path) {
+                Ok(hash) => {
+                    let entry = IndexEntry { path: path.clone(), size, mtime, hash };
+                    index.0.insert(path.clone(), entry.clone());
+                    diff.modified.push(entry);
+                    seen.insert(path.clone());
+                }
+                Err(_) => continue,
+            },
+            None => match hash_file(path) {
+                Ok(hash) => {
+                    let entry = IndexEntry { path: path.clone(), size, mtime, hash };
+                    index.0.insert(path.clone(), entry.clone());
+                    diff.added.push(entry);
+                    seen.insert(path.clone());
+                }
+                Err(_) => continue,
+            },
+        }
+    }
+
+    let removed: Vec<String> = index
+        .0
+        .keys()
+        .filter(|path| !seen.contains(*path))
+        .cloned()
+        .collect();
+    for path in &removed {
+        index.0.remove(path);
+    }
+    diff.removed = removed;
+
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_temp_file(name: &str, contents: &str) -> String {
+        let mut path = std::env::temp_dir();
+        path.push(format!("envie-index-test-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn new_files_are_added() {
+        let path = write_temp_file("added", "hello");
+        let mut index = ScanIndex::default();
+
+        let diff = diff_against_index(&mut index, &[path.clone()]).unwrap();
+
+        assert_eq!(diff.added.len(), 1);
+        assert!(diff.modified.is_empty());
+        assert!(diff.removed.is_empty());
+        assert!(index.0.contains_key(&path));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn unchanged_files_are_not_reported() {
+        let path = write_temp_file("unchanged", "hello");
+        let mut index = ScanIndex::default();
+        diff_against_index(&mut index, &[path.clone()]).unwrap();
+
+        let diff = diff_against_index(&mut index, &[path.clone()]).unwrap();
+
+        assert!(diff.added.is_empty());
+        assert!(diff.modified.is_empty());
+        assert!(diff.removed.is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn changed_content_is_reported_modified() {
+        let path = write_temp_file("modified", "hello");
+        let mut index = ScanIndex::default();
+        diff_against_index(&mut index, &[path.clone()]).unwrap();
+
+        // Different length is enough to force a re-hash even if mtime resolution is coarse.
+        std::fs::write(&path, "goodbye!!").unwrap();
+
+        let diff = diff_against_index(&mut index, &[path.clone()]).unwrap();
+
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.modified.len(), 1);
+        assert!(diff.removed.is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn missing_files_are_reported_removed() {
+        let path = write_temp_file("removed", "hello");
+        let mut index = ScanIndex::default();
+        diff_against_index(&mut index, &[path.clone()]).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let diff = diff_against_index(&mut index, &[]).unwrap();
+
+        assert_eq!(diff.removed, vec![path]);
+        assert!(index.0.is_empty());
+    }
+
+    #[test]
+    fn a_vanished_file_does_not_abort_the_whole_scan() {
+        let present = write_temp_file("present", "hello");
+        let gone = write_temp_file("gone", "bye");
+        std::fs::remove_file(&gone).unwrap();
+        let mut index = ScanIndex::default();
+
+        let diff = diff_against_index(&mut index, &[present.clone(), gone]).unwrap();
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].path, present);
+
+        std::fs::remove_file(&present).unwrap();
+    }
+}
@@ -1,27 +1,17 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+mod index;
+mod migration;
+mod parser;
+mod scan;
+mod settings;
+mod vault;
+mod watcher;
+
 use tauri::{Manager, TitleBarStyle, WebviewUrl, WebviewWindowBuilder};
-use walkdir::WalkDir;
 
-#[tauri::command]
-fn scan_for_configs() -> Vec<String> {
-    let mut files = Vec::new();
-    let path = "/Users/davidstranava/programming";
-    for entry in WalkDir::new(path)
-        .into_iter()
-        .filter_entry(|e| {
-            !e.file_name()
-                .to_string_lossy()
-                .eq_ignore_ascii_case("node_modules")
-        })
-        .filter_map(|e| e.ok())
-    {
-        let file_name = entry.file_name().to_string_lossy();
-        if file_name == ".env" || file_name == "config.local.yaml" {
-            files.push(entry.path().to_string_lossy().to_string());
-        }
-    }
-    files
-}
+use index::ScanIndexState;
+use settings::ScanSettingsState;
+use watcher::WatcherState;
 
 #[tauri::command]
 fn read_config_file(path: String) -> Result<String, String> {
@@ -33,29 +23,28 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+// We do NOT delete salt.txt here: it is shared by every per-user vault, so removing one
+// user's vault must not break key derivation for the others.
 #[tauri::command]
 fn nuke_vault(app: tauri::AppHandle, user_id: String) -> Result<(), String> {
     let local_data_dir = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
 
-    // We do NOT delete salt.txt anymore because it might be shared (or we assume single user per OS account?)
-    // If we want true multi-user, we should keep salt. But if loop fails, maybe we need to?
-    // Let's assume for now we only delete the specific vault file.
-
-    // Vault filename convention: "vault_<user_id>.hold"
-    let vault_name = format!("vault_{}.hold", user_id);
-    let vault_path = local_data_dir.join(&vault_name);
-
+    let vault_path = local_data_dir.join(format!("vault_{}.hold", user_id));
     if vault_path.exists() {
-         std::fs::remove_file(&vault_path).map_err(|e| e.to_string())?;
+        std::fs::remove_file(&vault_path).map_err(|e| e.to_string())?;
     }
 
-    // Also check standard filenames if user_id is empty or legacy?
-    if user_id == "legacy" || user_id.is_empty() {
-         let legacy_path = local_data_dir.join("vault.hold");
-         if legacy_path.exists() { std::fs::remove_file(&legacy_path).map_err(|e| e.to_string())?; }
+    // Pre-migration installs may still have an un-migrated legacy vault on disk.
+    if migration::is_legacy_user(&user_id) {
+        let legacy_vault = migration::legacy_vault_path(&local_data_dir);
+        if legacy_vault.exists() {
+            std::fs::remove_file(&legacy_vault).map_err(|e| e.to_string())?;
+        }
 
-         let snapshot_path = local_data_dir.join("snapshot.hold");
-         if snapshot_path.exists() { std::fs::remove_file(&snapshot_path).map_err(|e| e.to_string())?; }
+        let legacy_snapshot = migration::legacy_snapshot_path(&local_data_dir);
+        if legacy_snapshot.exists() {
+            std::fs::remove_file(&legacy_snapshot).map_err(|e| e.to_string())?;
+        }
     }
 
     Ok(())
@@ -65,17 +54,17 @@ fn nuke_vault(app: tauri::AppHandle, user_id: String) -> Result<(), String> {
 fn check_vault_exists(app: tauri::AppHandle, user_id: String) -> Result<bool, String> {
     let local_data_dir = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
 
-    let vault_name = format!("vault_{}.hold", user_id);
-    let vault_path = local_data_dir.join(&vault_name);
+    let vault_path = local_data_dir.join(format!("vault_{}.hold", user_id));
+    if vault_path.exists() {
+        return Ok(true);
+    }
 
-    // Legacy fallback check?
-    if !vault_path.exists() && (user_id == "legacy" || user_id.is_empty()) {
-        let legacy = local_data_dir.join("vault.hold");
-        let snapshot = local_data_dir.join("snapshot.hold");
-        return Ok(legacy.exists() || snapshot.exists());
+    // Pre-migration installs may still have an un-migrated legacy vault on disk.
+    if migration::is_legacy_user(&user_id) {
+        return Ok(migration::has_legacy_vault(&local_data_dir));
     }
 
-    Ok(vault_path.exists())
+    Ok(false)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -125,6 +114,14 @@ pub fn run() {
                 .join("salt.txt");
             app.handle().plugin(tauri_plugin_stronghold::Builder::with_argon2(&salt_path).build())?;
 
+            let scan_settings = settings::load_settings(&app.handle())?;
+            app.manage(ScanSettingsState(std::sync::Mutex::new(scan_settings)));
+
+            let scan_index = index::load_index(&app.handle())?;
+            app.manage(ScanIndexState(std::sync::Mutex::new(scan_index)));
+
+            app.manage(WatcherState(std::sync::Mutex::new(None)));
+
             Ok(())
         })
         .plugin(tauri_plugin_opener::init())
@@ -132,7 +129,17 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             greet,
-            scan_for_configs,
+            scan::scan_for_configs,
+            settings::get_scan_settings,
+            settings::update_scan_settings,
+            settings::add_scan_root,
+            settings::remove_scan_root,
+            watcher::start_watching,
+            watcher::stop_watching,
+            vault::export_vault,
+            vault::import_vault,
+            migration::migrate_legacy_vault,
+            parser::diff_configs,
             read_config_file,
             nuke_vault,
             check_vault_exists
@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+const MARKER_FILE: &str = "migration.version";
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+pub fn legacy_vault_path(dir: &Path) -> PathBuf {
+    dir.join("vault.hold")
+}
+
+pub fn legacy_snapshot_path(dir: &Path) -> PathBuf {
+    dir.join("snapshot.hold")
+}
+
+pub fn has_legacy_vault(dir: &Path) -> bool {
+    legacy_vault_path(dir).exists() || legacy_snapshot_path(dir).exists()
+}
+
+/// The single place that decides which `user_id`s fall back to the pre-migration,
+/// single-user vault naming (`vault.hold`/`snapshot.hold`) instead of `vault_<user_id>.hold`.
+pub fn is_legacy_user(user_id: &str) -> bool {
+    user_id == "legacy" || user_id.is_empty()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub migrated: bool,
+    pub user_id: Option<String>,
+}
+
+fn marker_path(dir: &Path) -> PathBuf {
+    dir.join(MARKER_FILE)
+}
+
+fn already_migrated(dir: &Path) -> bool {
+    match std::fs::read_to_string(marker_path(dir)) {
+        Ok(raw) => raw.trim().parse::<u32>().map(|v| v >= CURRENT_SCHEMA_VERSION).unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
+fn write_marker(dir: &Path) -> Result<(), String> {
+    std::fs::write(marker_path(dir), CURRENT_SCHEMA_VERSION.to_string()).map_err(|e| e.to_string())
+}
+
+/// Detects an old single-user `vault.hold`/`snapshot.hold` and renames it to the
+/// `vault_<user_id>.hold` convention, leaving `salt.txt` untouched. Safe to call on every
+/// startup: it is a no-op once the schema marker records that migration already ran.
+#[tauri::command]
+pub fn migrate_legacy_vault(app: AppHandle, user_id: String) -> Result<MigrationReport, String> {
+    let local_data_dir = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&local_data_dir).map_err(|e| e.to_string())?;
+
+    if already_migrated(&local_data_dir) {
+        return Ok(MigrationReport { migrated: false, user_id: None });
+    }
+
+    if !has_legacy_vault(&local_data_dir) {
+        write_marker(&local_data_dir)?;
+        return Ok(MigrationReport { migrated: false, user_id: None });
+    }
+
+    let _ = app.emit("vault-migration-progress", "migrating legacy vault");
+
+    let target = local_data_dir.join(format!("vault_{}.hold", user_id));
+    if target.exists() {
+        let message = format!("migration target {} already exists", target.display());
+        let _ = app.emit("vault-migration-error", &message);
+        return Err(message);
+    }
+
+    let legacy_vault = legacy_vault_path(&local_data_dir);
+    let legacy_snapshot = legacy_snapshot_path(&local_data_dir);
+    let source = if legacy_vault.exists() { legacy_vault.clone() } else { legacy_snapshot.clone() };
+
+    std::fs::rename(&source, &target).map_err(|e| {
+        let message = e.to_string();
+        let _ = app.emit("vault-migration-error", &message);
+        message
+    })?;
+
+    // `vault.hold` takes priority above, so if both it and `snapshot.hold` were present the
+    // snapshot is left under its own name rather than silently dropped -- flag that so it
+    // doesn't go unnoticed now that the marker below makes this a one-shot migration.
+    if source != legacy_snapshot && legacy_snapshot.exists() {
+        let _ = app.emit(
+            "vault-migration-progress",
+            format!("legacy snapshot retained at {}", legacy_snapshot.display()),
+        );
+    }
+
+    write_marker(&local_data_dir)?;
+    let _ = app.emit("vault-migration-progress", "migration complete");
+
+    Ok(MigrationReport { migrated: true, user_id: Some(user_id) })
+}
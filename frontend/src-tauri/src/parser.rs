@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+/// Parses a `.env`-style file: `KEY=VALUE` pairs, blank lines, `#` comments, optional
+/// `export` prefixes, and single/double-quoted values are all handled.
+pub fn parse_env(contents: &str) -> BTreeMap<String, String> {
+    let mut map = BTreeMap::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        let key = key.trim();
+        let mut value = value.trim();
+        if !(value.starts_with('"') || value.starts_with('\'')) {
+            if let Some(idx) = value.find(" #") {
+                value = value[..idx].trim();
+            }
+        }
+
+        map.insert(key.to_string(), unquote(value));
+    }
+
+    map
+}
+
+fn unquote(value: &str) -> String {
+    let bytes = value.as_bytes();
+    if bytes.len() >= 2
+        && ((bytes[0] == b'"' && bytes[bytes.len() - 1] == b'"')
+            || (bytes[0] == b'\'' && bytes[bytes.len() - 1] == b'\''))
+    {
+        return value[1..value.len() - 1].to_string();
+    }
+    value.to_string()
+}
+
+/// Parses a `config.local.yaml`-style file into a flat `dotted.key -> value` map.
+pub fn parse_yaml(contents: &str) -> Result<BTreeMap<String, String>, String> {
+    let value: serde_yaml::Value = serde_yaml::from_str(contents).map_err(|e| e.to_string())?;
+    let mut map = BTreeMap::new();
+    flatten_yaml("", &value, &mut map);
+    Ok(map)
+}
+
+fn flatten_yaml(prefix: &str, value: &serde_yaml::Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            for (key, nested) in mapping {
+                let key = key.as_str().unwrap_or_default();
+                let full_key =
+                    if prefix.is_empty() { key.to_string() } else { format!("{prefix}.{key}") };
+                flatten_yaml(&full_key, nested, out);
+            }
+        }
+        serde_yaml::Value::Null => {
+            out.insert(prefix.to_string(), String::new());
+        }
+        other => {
+            out.insert(prefix.to_string(), scalar_to_string(other));
+        }
+    }
+}
+
+fn scalar_to_string(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => s.clone(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        _ => serde_yaml::to_string(value).unwrap_or_default().trim().to_string(),
+    }
+}
+
+fn parse_config_file(path: &Path, contents: &str) -> Result<BTreeMap<String, String>, String> {
+    let is_yaml = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+        .unwrap_or(false);
+
+    if is_yaml {
+        parse_yaml(contents)
+    } else {
+        Ok(parse_env(contents))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyDiff {
+    pub key: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfigDiff {
+    pub added: Vec<KeyDiff>,
+    pub removed: Vec<KeyDiff>,
+    pub changed: Vec<KeyDiff>,
+}
+
+/// Masks a secret value for display, keeping only its rough length as a hint.
+fn mask(value: &str) -> String {
+    "*".repeat(value.chars().count().clamp(4, 8))
+}
+
+fn present(value: &str, reveal: bool) -> String {
+    if reveal {
+        value.to_string()
+    } else {
+        mask(value)
+    }
+}
+
+/// Diffs the normalized keys of two config files. Values are masked unless `reveal` is set.
+#[tauri::command]
+pub fn diff_configs(left_path: String, right_path: String, reveal: bool) -> Result<ConfigDiff, String> {
+    let left_contents = std::fs::read_to_string(&left_path).map_err(|e| e.to_string())?;
+    let right_contents = std::fs::read_to_string(&right_path).map_err(|e| e.to_string())?;
+
+    let left = parse_config_file(Path::new(&left_path), &left_contents)?;
+    let right = parse_config_file(Path::new(&right_path), &right_contents)?;
+
+    let mut diff = ConfigDiff::default();
+
+    for (key, right_value) in &right {
+        match left.get(key) {
+            None => diff.added.push(KeyDiff {
+                key: key.clone(),
+                left: None,
+                right: Some(present(right_value, reveal)),
+            }),
+            Some(left_value) if left_value != right_value => diff.changed.push(KeyDiff {
+                key: key.clone(),
+                left: Some(present(left_value, reveal)),
+                right: Some(present(right_value, reveal)),
+            }),
+            _ => {}
+        }
+    }
+
+    for (key, left_value) in &left {
+        if !right.contains_key(key) {
+            diff.removed.push(KeyDiff {
+                key: key.clone(),
+                left: Some(present(left_value, reveal)),
+                right: None,
+            });
+        }
+    }
+
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_simple_pairs() {
+        let map = parse_env("KEY=value\nOTHER=123");
+        assert_eq!(map.get("KEY").map(String::as_str), Some("value"));
+        assert_eq!(map.get("OTHER").map(String::as_str), Some("123"));
+    }
+
+    #[test]
+    fn skips_blank_lines_and_comments() {
+        let map = parse_env("\n# a comment\nKEY=value\n");
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get("KEY").map(String::as_str), Some("value"));
+    }
+
+    #[test]
+    fn strips_export_prefix() {
+        let map = parse_env("export KEY=value");
+        assert_eq!(map.get("KEY").map(String::as_str), Some("value"));
+    }
+
+    #[test]
+    fn unquotes_double_and_single_quoted_values() {
+        let map = parse_env("A=\"double\"\nB='single'");
+        assert_eq!(map.get("A").map(String::as_str), Some("double"));
+        assert_eq!(map.get("B").map(String::as_str), Some("single"));
+    }
+
+    #[test]
+    fn strips_inline_comments_outside_quotes_only() {
+        let map = parse_env("A=value # trailing comment\nB=\"value # not a comment\"");
+        assert_eq!(map.get("A").map(String::as_str), Some("value"));
+        assert_eq!(map.get("B").map(String::as_str), Some("value # not a comment"));
+    }
+
+    #[test]
+    fn flattens_nested_yaml_keys() {
+        let map = parse_yaml("database:\n  host: localhost\n  port: 5432\ndebug: true").unwrap();
+        assert_eq!(map.get("database.host").map(String::as_str), Some("localhost"));
+        assert_eq!(map.get("database.port").map(String::as_str), Some("5432"));
+        assert_eq!(map.get("debug").map(String::as_str), Some("true"));
+    }
+
+    #[test]
+    fn present_masks_by_default_and_reveals_on_request() {
+        assert_ne!(present("abc", false), "abc");
+        assert_eq!(present("abc", true), "abc");
+    }
+}
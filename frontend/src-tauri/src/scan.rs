@@ -0,0 +1,66 @@
+use tauri::{AppHandle, State};
+use walkdir::WalkDir;
+
+use crate::index::{self, ScanDiff, ScanIndexState};
+use crate::settings::ScanSettingsState;
+
+pub(crate) fn matches_any(file_name: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|pattern| {
+        glob::Pattern::new(pattern)
+            .map(|p| p.matches(file_name))
+            .unwrap_or(false)
+    })
+}
+
+fn walk_configured_roots(settings: &crate::settings::ScanSettings) -> Vec<String> {
+    let mut files = Vec::new();
+    for root in &settings.roots {
+        for entry in WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                !settings
+                    .ignore
+                    .iter()
+                    .any(|ignored| e.file_name().to_string_lossy().eq_ignore_ascii_case(ignored))
+            })
+            .filter_map(|e| e.ok())
+        {
+            let file_name = entry.file_name().to_string_lossy();
+            if matches_any(&file_name, &settings.patterns) {
+                files.push(entry.path().to_string_lossy().to_string());
+            }
+        }
+    }
+    files
+}
+
+/// Walks the configured roots for files matching the configured patterns, then diffs the
+/// result against the on-disk index so only changed files are re-hashed. The returned
+/// `ScanDiff` tells the frontend exactly what changed since the last scan.
+///
+/// The walk, the hashing inside `diff_against_index`, and the index write-back are all
+/// synchronous filesystem work, so they all run inside `spawn_blocking` rather than on the
+/// async body -- otherwise a large root (or a burst of modified files) would stall the Tokio
+/// worker pool for every other in-flight command, including the watcher's event emits.
+#[tauri::command]
+pub async fn scan_for_configs(
+    app: AppHandle,
+    settings_state: State<'_, ScanSettingsState>,
+    index_state: State<'_, ScanIndexState>,
+) -> Result<ScanDiff, String> {
+    let settings = settings_state.0.lock().unwrap().clone();
+
+    let mut scan_index = index_state.0.lock().unwrap().clone();
+    let (diff, scan_index) = tauri::async_runtime::spawn_blocking(move || {
+        let files = walk_configured_roots(&settings);
+        let diff = index::diff_against_index(&mut scan_index, &files)?;
+        index::save_index(&app, &scan_index)?;
+        Ok::<_, String>((diff, scan_index))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    *index_state.0.lock().unwrap() = scan_index;
+
+    Ok(diff)
+}
@@ -0,0 +1,122 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_dialog::DialogExt;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// User-configurable scan configuration, persisted to `app_local_data_dir()/settings.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSettings {
+    /// Directories the scanner walks looking for config files.
+    pub roots: Vec<String>,
+    /// Filenames or glob patterns (e.g. `.env`, `.env.*`, `config.local.yaml`) to pick up.
+    pub patterns: Vec<String>,
+    /// Directory names to skip entirely while walking (e.g. `node_modules`).
+    pub ignore: Vec<String>,
+}
+
+impl Default for ScanSettings {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            patterns: vec![".env".into(), "config.local.yaml".into()],
+            ignore: vec!["node_modules".into()],
+        }
+    }
+}
+
+pub struct ScanSettingsState(pub Mutex<ScanSettings>);
+
+fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(SETTINGS_FILE))
+}
+
+/// Loads the persisted settings, falling back to defaults on a missing or corrupted file
+/// (e.g. truncated by a crash mid-write, since `std::fs::write` isn't atomic) rather than
+/// failing -- this is called from `setup`, where an `Err` would abort app startup entirely.
+pub fn load_settings(app: &AppHandle) -> Result<ScanSettings, String> {
+    let path = settings_path(app)?;
+    if !path.exists() {
+        return Ok(ScanSettings::default());
+    }
+    let raw = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    match serde_json::from_str(&raw) {
+        Ok(settings) => Ok(settings),
+        Err(e) => {
+            eprintln!("failed to parse {}: {e}; falling back to defaults", path.display());
+            Ok(ScanSettings::default())
+        }
+    }
+}
+
+fn save_settings(app: &AppHandle, settings: &ScanSettings) -> Result<(), String> {
+    let path = settings_path(app)?;
+    let raw = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(path, raw).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_scan_settings(state: State<ScanSettingsState>) -> ScanSettings {
+    state.0.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn update_scan_settings(
+    app: AppHandle,
+    state: State<ScanSettingsState>,
+    settings: ScanSettings,
+) -> Result<ScanSettings, String> {
+    save_settings(&app, &settings)?;
+    *state.0.lock().unwrap() = settings.clone();
+    Ok(settings)
+}
+
+/// Opens a native folder picker and appends the chosen directory to the configured roots.
+/// Uses the callback-based `pick_folder` rather than `blocking_pick_folder` so waiting for
+/// the user to interact with the native dialog doesn't tie up an async runtime worker
+/// thread that other in-flight commands (e.g. `scan_for_configs`) need.
+#[tauri::command]
+pub async fn add_scan_root(
+    app: AppHandle,
+    state: State<'_, ScanSettingsState>,
+) -> Result<ScanSettings, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog().file().pick_folder(move |folder| {
+        let _ = tx.send(folder);
+    });
+    let folder = rx
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "no folder selected".to_string())?;
+
+    let path = folder.to_string();
+    let settings = {
+        let mut guard = state.0.lock().unwrap();
+        if !guard.roots.iter().any(|r| r == &path) {
+            guard.roots.push(path);
+        }
+        guard.clone()
+    };
+    save_settings(&app, &settings)?;
+    Ok(settings)
+}
+
+#[tauri::command]
+pub fn remove_scan_root(
+    app: AppHandle,
+    state: State<ScanSettingsState>,
+    root: String,
+) -> Result<ScanSettings, String> {
+    let settings = {
+        let mut guard = state.0.lock().unwrap();
+        guard.roots.retain(|r| r != &root);
+        guard.clone()
+    };
+    save_settings(&app, &settings)?;
+    Ok(settings)
+}
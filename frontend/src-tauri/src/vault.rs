@@ -0,0 +1,243 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, OsRng};
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+const MAGIC: &[u8; 8] = b"ENVIEVLT";
+const VERSION: u8 = 1;
+const ARCHIVE_SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+// Mirrors the Argon2id parameters tauri-plugin-stronghold's `with_argon2()` actually derives
+// its own key with (rust-argon2's `Config::owasp2()` default: mem_cost = 19456 KiB, time_cost
+// = 2, lanes = 1), so exported archives cost the same to brute-force as the vault they came
+// from -- not some other, unrelated cost.
+const ARGON2_M_COST: u32 = 19_456; // 19 MiB
+const ARGON2_T_COST: u32 = 2;
+const ARGON2_P_COST: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultArchiveHeader {
+    magic: [u8; 8],
+    version: u8,
+    argon2_m_cost: u32,
+    argon2_t_cost: u32,
+    argon2_p_cost: u32,
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+}
+
+/// Everything needed to restore a vault on another machine.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+struct VaultBundle {
+    vault: Vec<u8>,
+    salt: Vec<u8>,
+    snapshot: Option<Vec<u8>>,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], m: u32, t: u32, p: u32) -> Result<[u8; 32], String> {
+    let params = Params::new(m, t, p, Some(32)).map_err(|e| e.to_string())?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// Encrypts `bundle` under `passphrase`, returning the full archive bytes (header + ciphertext).
+fn encrypt_bundle(bundle: &VaultBundle, passphrase: &str) -> Result<Vec<u8>, String> {
+    let plaintext = serde_json::to_vec(bundle).map_err(|e| e.to_string())?;
+
+    let mut archive_salt = [0u8; ARCHIVE_SALT_LEN];
+    OsRng.fill_bytes(&mut archive_salt);
+    let key = derive_key(passphrase, &archive_salt, ARGON2_M_COST, ARGON2_T_COST, ARGON2_P_COST)?;
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = XNonce::from_slice(&nonce_bytes);
+
+    let cipher = XChaCha20Poly1305::new((&key).into());
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext.as_ref())
+        .map_err(|e| e.to_string())?;
+
+    let header = VaultArchiveHeader {
+        magic: *MAGIC,
+        version: VERSION,
+        argon2_m_cost: ARGON2_M_COST,
+        argon2_t_cost: ARGON2_T_COST,
+        argon2_p_cost: ARGON2_P_COST,
+        salt: archive_salt.to_vec(),
+        nonce: nonce_bytes.to_vec(),
+    };
+    let header_bytes = serde_json::to_vec(&header).map_err(|e| e.to_string())?;
+
+    let mut out = Vec::with_capacity(4 + header_bytes.len() + ciphertext.len());
+    out.extend_from_slice(&(header_bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(&header_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypts an archive produced by [`encrypt_bundle`]. Every field taken from the archive
+/// header is untrusted input, so lengths are validated before being handed to the
+/// fixed-size nonce/salt APIs, which would otherwise panic on a corrupted file.
+fn decrypt_bundle(archive: &[u8], passphrase: &str) -> Result<VaultBundle, String> {
+    if archive.len() < 4 {
+        return Err("archive is truncated or not an Envie vault archive".into());
+    }
+    let header_len = u32::from_le_bytes(archive[0..4].try_into().unwrap()) as usize;
+    let header_bytes = archive
+        .get(4..4 + header_len)
+        .ok_or_else(|| "archive is truncated or not an Envie vault archive".to_string())?;
+    let header: VaultArchiveHeader =
+        serde_json::from_slice(header_bytes).map_err(|e| e.to_string())?;
+
+    if &header.magic != MAGIC {
+        return Err("not an Envie vault archive".into());
+    }
+    if header.version != VERSION {
+        return Err(format!("unsupported vault archive version {}", header.version));
+    }
+    if header.salt.len() != ARCHIVE_SALT_LEN {
+        return Err("corrupted vault archive: invalid salt length".into());
+    }
+    if header.nonce.len() != NONCE_LEN {
+        return Err("corrupted vault archive: invalid nonce length".into());
+    }
+
+    let ciphertext = archive
+        .get(4 + header_len..)
+        .ok_or_else(|| "archive is truncated or not an Envie vault archive".to_string())?;
+    let key = derive_key(
+        passphrase,
+        &header.salt,
+        header.argon2_m_cost,
+        header.argon2_t_cost,
+        header.argon2_p_cost,
+    )?;
+    let nonce = XNonce::from_slice(&header.nonce);
+    let cipher = XChaCha20Poly1305::new((&key).into());
+    let plaintext = cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| "failed to decrypt archive: wrong passphrase or corrupted file".to_string())?;
+
+    serde_json::from_slice(&plaintext).map_err(|e| e.to_string())
+}
+
+/// Bundles the vault, its salt, and any snapshot into a single password-protected archive
+/// encrypted with Argon2id-derived XChaCha20-Poly1305, so a user can back up or move their
+/// secrets between machines.
+#[tauri::command]
+pub fn export_vault(
+    app: AppHandle,
+    user_id: String,
+    passphrase: String,
+    destination: String,
+) -> Result<(), String> {
+    let local_data_dir = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
+    let vault_path = local_data_dir.join(format!("vault_{}.hold", user_id));
+    let salt_path = local_data_dir.join("salt.txt");
+    let snapshot_path = local_data_dir.join("snapshot.hold");
+
+    let vault = std::fs::read(&vault_path).map_err(|e| e.to_string())?;
+    let salt = std::fs::read(&salt_path).map_err(|e| e.to_string())?;
+    let snapshot = if snapshot_path.exists() {
+        Some(std::fs::read(&snapshot_path).map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    let archive = encrypt_bundle(&VaultBundle { vault, salt, snapshot }, &passphrase)?;
+    std::fs::write(&destination, archive).map_err(|e| e.to_string())
+}
+
+/// Restores a vault archive produced by [`export_vault`]. Refuses to clobber an existing
+/// per-user vault unless `force` is set. `salt.txt` is shared by every vault on the
+/// machine, so it is only ever written when it doesn't already exist; if it exists and
+/// doesn't match the archive's salt, the import is rejected rather than silently re-keying
+/// (and thereby bricking) every other vault on the machine.
+#[tauri::command]
+pub fn import_vault(
+    app: AppHandle,
+    user_id: String,
+    passphrase: String,
+    source: String,
+    force: bool,
+) -> Result<(), String> {
+    let raw = std::fs::read(&source).map_err(|e| e.to_string())?;
+    let bundle = decrypt_bundle(&raw, &passphrase)?;
+
+    let local_data_dir = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
+    let vault_path = local_data_dir.join(format!("vault_{}.hold", user_id));
+    let salt_path = local_data_dir.join("salt.txt");
+    let snapshot_path = local_data_dir.join("snapshot.hold");
+
+    if vault_path.exists() && !force {
+        return Err("a vault already exists for this user; pass force to overwrite".into());
+    }
+
+    std::fs::create_dir_all(&local_data_dir).map_err(|e| e.to_string())?;
+
+    if salt_path.exists() {
+        let existing_salt = std::fs::read(&salt_path).map_err(|e| e.to_string())?;
+        if existing_salt != bundle.salt {
+            return Err(
+                "salt mismatch: salt.txt is shared by every vault on this machine; importing this archive would make the other vaults undecryptable".into(),
+            );
+        }
+    } else {
+        std::fs::write(&salt_path, &bundle.salt).map_err(|e| e.to_string())?;
+    }
+
+    std::fs::write(&vault_path, &bundle.vault).map_err(|e| e.to_string())?;
+    if let Some(snapshot) = &bundle.snapshot {
+        std::fs::write(&snapshot_path, snapshot).map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_bundle() -> VaultBundle {
+        VaultBundle {
+            vault: b"top secret vault bytes".to_vec(),
+            salt: b"0123456789abcdef".to_vec(),
+            snapshot: Some(b"snapshot bytes".to_vec()),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_encrypt_and_decrypt() {
+        let bundle = sample_bundle();
+        let archive = encrypt_bundle(&bundle, "correct horse battery staple").unwrap();
+        let decrypted = decrypt_bundle(&archive, "correct horse battery staple").unwrap();
+        assert_eq!(bundle, decrypted);
+    }
+
+    #[test]
+    fn rejects_wrong_passphrase() {
+        let bundle = sample_bundle();
+        let archive = encrypt_bundle(&bundle, "correct horse battery staple").unwrap();
+        assert!(decrypt_bundle(&archive, "wrong passphrase").is_err());
+    }
+
+    #[test]
+    fn rejects_corrupted_header() {
+        let bundle = sample_bundle();
+        let mut archive = encrypt_bundle(&bundle, "pw").unwrap();
+        archive[4] = archive[4].wrapping_add(1); // corrupt a byte inside the header JSON
+        assert!(decrypt_bundle(&archive, "pw").is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_archive() {
+        assert!(decrypt_bundle(&[0, 1, 2], "pw").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::scan;
+use crate::settings::{ScanSettings, ScanSettingsState};
+
+/// How long to wait after the last event on a path before emitting a change for it, so a
+/// burst of writes (e.g. an editor save) collapses into a single notification.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Holds the live watcher so it stays alive for the app's lifetime. `None` means "not watching".
+pub struct WatcherState(pub Mutex<Option<RecommendedWatcher>>);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigChangeEvent {
+    pub path: String,
+    pub kind: &'static str,
+}
+
+fn classify(kind: &EventKind) -> Option<&'static str> {
+    match kind {
+        EventKind::Create(_) => Some("created"),
+        EventKind::Modify(_) => Some("modified"),
+        EventKind::Remove(_) => Some("removed"),
+        _ => None,
+    }
+}
+
+/// Whether a changed path is a config file we care about: its name matches a configured
+/// pattern and none of its ancestor directories is in the configured ignore list.
+fn is_relevant(path: &Path, settings: &ScanSettings) -> bool {
+    let ignored = path.components().any(|component| {
+        settings
+            .ignore
+            .iter()
+            .any(|ignore| component.as_os_str().to_string_lossy().eq_ignore_ascii_case(ignore))
+    });
+    if ignored {
+        return false;
+    }
+
+    match path.file_name() {
+        Some(file_name) => scan::matches_any(&file_name.to_string_lossy(), &settings.patterns),
+        None => false,
+    }
+}
+
+/// Watches `roots` recursively and emits a debounced `config-changed` event for every
+/// create/modify/delete of a file matching the configured patterns. Replaces any watcher
+/// already running.
+#[tauri::command]
+pub fn start_watching(
+    app: AppHandle,
+    state: State<WatcherState>,
+    settings_state: State<ScanSettingsState>,
+    roots: Vec<String>,
+) -> Result<(), String> {
+    let settings = settings_state.0.lock().unwrap().clone();
+
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher =
+        notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    for root in &roots {
+        watcher
+            .watch(std::path::Path::new(root), RecursiveMode::Recursive)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        let mut pending: HashMap<PathBuf, (&'static str, Instant)> = HashMap::new();
+
+        loop {
+            match rx.recv_timeout(Duration::from_millis(50)) {
+                Ok(Ok(event)) => {
+                    if let Some(kind) = classify(&event.kind) {
+                        for path in event.paths {
+                            if is_relevant(&path, &settings) {
+                                pending.insert(path, (kind, Instant::now()));
+                            }
+                        }
+                    }
+                }
+                Ok(Err(_)) => {}
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            let ready: Vec<PathBuf> = pending
+                .iter()
+                .filter(|(_, (_, seen))| seen.elapsed() >= DEBOUNCE)
+                .map(|(path, _)| path.clone())
+                .collect();
+
+            for path in ready {
+                if let Some((kind, _)) = pending.remove(&path) {
+                    let _ = app_handle.emit(
+                        "config-changed",
+                        ConfigChangeEvent { path: path.to_string_lossy().to_string(), kind },
+                    );
+                }
+            }
+        }
+    });
+
+    *state.0.lock().unwrap() = Some(watcher);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_watching(state: State<WatcherState>) -> Result<(), String> {
+    *state.0.lock().unwrap() = None;
+    Ok(())
+}